@@ -1,36 +1,58 @@
 use serde::Serialize;
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
 use std::iter;
 use strum;
-use strum::{EnumIter, EnumString};
+use strum::{EnumIter, EnumString, IntoEnumIterator};
 use uuid::Uuid;
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, PartialEq, Eq, Hash, Clone)]
 pub struct Player {
     uuid: Uuid,
     name: String,
 }
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct BreakCount(u64);
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct BuildCount(u64);
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct PlayTicks(u64);
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct VoteCount(u64);
 
-pub trait AggregatedPlayerAttribution: Ord + Clone {}
+pub trait AggregatedPlayerAttribution: Ord + Clone + Hash {
+    /// The raw value of this attribution, used to normalize it against the
+    /// largest value seen for the same metric (see [`FederatedRanking`]).
+    fn as_f64(&self) -> f64;
+}
 
-impl AggregatedPlayerAttribution for BreakCount {}
-impl AggregatedPlayerAttribution for BuildCount {}
-impl AggregatedPlayerAttribution for PlayTicks {}
-impl AggregatedPlayerAttribution for VoteCount {}
+impl AggregatedPlayerAttribution for BreakCount {
+    fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+}
+impl AggregatedPlayerAttribution for BuildCount {
+    fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+}
+impl AggregatedPlayerAttribution for PlayTicks {
+    fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+}
+impl AggregatedPlayerAttribution for VoteCount {
+    fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+}
 
-#[derive(Clone)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub struct AttributionRecord<Attribution: AggregatedPlayerAttribution> {
     pub player: Player,
     pub attribution: Attribution,
@@ -42,6 +64,16 @@ pub struct RankedAttributionRecord<Attribution: AggregatedPlayerAttribution> {
     pub attribution_record: AttributionRecord<Attribution>,
 }
 
+/// A [`RankedAttributionRecord`] with its `rank` normalized into
+/// `local_score`, a `[0, 1]` figure for rendering progress bars or for
+/// cutting off low-ranked noise. The top player always scores `1.0`.
+#[derive(Clone)]
+pub struct ScoredRankedAttributionRecord<Attribution: AggregatedPlayerAttribution> {
+    pub rank: u32,
+    pub attribution_record: AttributionRecord<Attribution>,
+    pub local_score: f64,
+}
+
 pub struct Ranking<Attribution: AggregatedPlayerAttribution> {
     /// invariant: these records are sorted and given "ranks" so that
     ///  - `.rank` is increasing
@@ -49,86 +81,554 @@ pub struct Ranking<Attribution: AggregatedPlayerAttribution> {
     ///    - `sorted_ranked_records[i].rank.0` equals `i + 1` (i.e. there is only one record with rank i + 1), or
     ///    - there is some r < i such that `sorted_ranked_records[j].rank.0 == r + 1` for all r ≤ j ≤ i (i.e. there are ties)
     sorted_ranked_records: Vec<RankedAttributionRecord<Attribution>>,
+    /// The strategy `sorted_ranked_records` was last ranked with, so that
+    /// [`Ranking::apply_delta`] can recompute ranks the same way without
+    /// being told the strategy again.
+    strategy: RankingStrategy,
 }
 
 pub struct RankingSlice<Attribution: AggregatedPlayerAttribution>(
-    pub Vec<RankedAttributionRecord<Attribution>>,
+    pub Vec<ScoredRankedAttributionRecord<Attribution>>,
 );
 
 impl<Attribution: AggregatedPlayerAttribution + Clone> Default for Ranking<Attribution> {
     fn default() -> Self {
         Ranking {
             sorted_ranked_records: vec![],
+            strategy: RankingStrategy::default(),
         }
     }
 }
 
-impl<Attribution: AggregatedPlayerAttribution + Clone> Ranking<Attribution> {
-    pub fn hydrate_record_set(&mut self, records: HashSet<AttributionRecord<Attribution>>) {
-        struct ScanState<Attribution> {
-            next_item_index: usize,
-            previous_attribution: Attribution,
-            previous_item_rank: u32,
+/// How ties in attribution value are reflected in `.rank`. Different
+/// leaderboards want different tie semantics: dense ranking for compact
+/// display, ordinal for award cutoffs where every player needs a distinct
+/// placement.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RankingStrategy {
+    /// Ties share a rank and the next rank skips, i.e. 1,2,2,4. This is the
+    /// ranking `hydrate_record_set` has always produced.
+    #[default]
+    Competition,
+    /// Ties share a rank and the next rank does not skip, i.e. 1,2,2,3.
+    Dense,
+    /// No ties: every record gets a distinct, increasing rank, i.e. 1,2,3,4,
+    /// breaking ties by `Player.uuid`.
+    Ordinal,
+}
+
+/// Assigns ranks per `strategy` to items already sorted descending by
+/// whatever score `compare` orders them on, comparing each item against the
+/// one right before it. `Ranking`'s full hydration paths and
+/// `FederatedRanking`'s merged board compute ranks by calling this with their
+/// own comparator. `Ranking::recompute_ranks_from`, used by the incremental
+/// `apply_delta`/`apply_deltas` path, applies the same per-strategy rules in
+/// its own scan that resumes from an already-ranked preceding record.
+fn assign_ranks_descending<T>(
+    sorted_descending_items: &[T],
+    strategy: RankingStrategy,
+    compare: impl Fn(&T, &T) -> Ordering,
+) -> Vec<u32> {
+    let mut ranks = Vec::with_capacity(sorted_descending_items.len());
+    let mut previous_rank: u32 = 1;
+
+    for (index, item) in sorted_descending_items.iter().enumerate() {
+        let next_rank = if index == 0 {
+            1
+        } else {
+            let ordering = compare(&sorted_descending_items[index - 1], item);
+            let is_tie = ordering == Ordering::Equal;
+            assert!(is_tie || ordering == Ordering::Greater);
+
+            match strategy {
+                RankingStrategy::Competition => {
+                    if is_tie {
+                        previous_rank
+                    } else {
+                        (index as u32) + 1
+                    }
+                }
+                RankingStrategy::Dense => {
+                    if is_tie {
+                        previous_rank
+                    } else {
+                        previous_rank + 1
+                    }
+                }
+                RankingStrategy::Ordinal => previous_rank + 1,
+            }
+        };
+
+        ranks.push(next_rank);
+        previous_rank = next_rank;
+    }
+
+    ranks
+}
+
+/// Assigns ranks per `strategy` to a slice of records already sorted by
+/// attribution descending (and, for [`RankingStrategy::Ordinal`], by
+/// `Player.uuid` within ties). Shared by [`Ranking::hydrate_record_set`] and
+/// [`Ranking::top_k`] so both full and bounded hydration agree on tie
+/// semantics.
+fn rank_sorted_descending<Attribution: AggregatedPlayerAttribution>(
+    sorted_descending_records: Vec<AttributionRecord<Attribution>>,
+    strategy: RankingStrategy,
+) -> Vec<RankedAttributionRecord<Attribution>> {
+    let ranks = assign_ranks_descending(&sorted_descending_records, strategy, |previous, current| {
+        previous.attribution.cmp(&current.attribution)
+    });
+
+    sorted_descending_records
+        .into_iter()
+        .zip(ranks)
+        .map(|(attribution_record, rank)| RankedAttributionRecord {
+            rank,
+            attribution_record,
+        })
+        .collect()
+}
+
+/// Sorts `records` by attribution descending, breaking ties by `Player.uuid`
+/// when `strategy` is [`RankingStrategy::Ordinal`] so every record ends up
+/// with a distinct, deterministic position.
+fn sort_descending_for_strategy<Attribution: AggregatedPlayerAttribution>(
+    records: &mut [AttributionRecord<Attribution>],
+    strategy: RankingStrategy,
+) {
+    match strategy {
+        RankingStrategy::Ordinal => records.sort_by(|a, b| {
+            b.attribution
+                .cmp(&a.attribution)
+                .then_with(|| a.player.uuid.cmp(&b.player.uuid))
+        }),
+        RankingStrategy::Competition | RankingStrategy::Dense => {
+            records.sort_by_key(|ar| ar.attribution.clone());
+            records.reverse();
         }
+    }
+}
+
+/// Clamps `offset..(offset + limit)` to `items`' bounds before slicing, so
+/// asking for a page past the end of a short list returns whatever's left
+/// instead of panicking. Shared by every `paginate`/`paginate_above` method
+/// in this module.
+fn paginate_slice<T: Clone>(items: &[T], offset: usize, limit: usize) -> Vec<T> {
+    let start = offset.min(items.len());
+    let end = offset.saturating_add(limit).min(items.len());
+    items[start..end].to_vec()
+}
+
+/// Wraps an [`AttributionRecord`] so that ordering it puts the *smallest*
+/// attribution value on top of a [`BinaryHeap`] (which is otherwise a
+/// max-heap), letting [`Ranking::top_k`] use the heap as a bounded min-heap.
+/// Ties are broken deterministically by `Player.uuid` so the eviction order
+/// is stable across runs.
+struct MinHeapEntry<Attribution: AggregatedPlayerAttribution>(AttributionRecord<Attribution>);
 
+impl<Attribution: AggregatedPlayerAttribution> PartialEq for MinHeapEntry<Attribution> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Attribution: AggregatedPlayerAttribution> Eq for MinHeapEntry<Attribution> {}
+
+impl<Attribution: AggregatedPlayerAttribution> PartialOrd for MinHeapEntry<Attribution> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Attribution: AggregatedPlayerAttribution> Ord for MinHeapEntry<Attribution> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .attribution
+            .cmp(&self.0.attribution)
+            .then_with(|| other.0.player.uuid.cmp(&self.0.player.uuid))
+    }
+}
+
+impl<Attribution: AggregatedPlayerAttribution + Clone> Ranking<Attribution> {
+    pub fn hydrate_record_set(
+        &mut self,
+        records: HashSet<AttributionRecord<Attribution>>,
+        strategy: RankingStrategy,
+    ) {
         let mut records = records.into_iter().collect::<Vec<_>>();
-        records.sort_by_key(|ar| ar.attribution.clone());
-        records.reverse();
-
-        let (first_record, tail_records) = match records.as_slice() {
-            [first, rest @ ..] => (first, rest),
-            [] => {
-                self.sorted_ranked_records = vec![];
-                return;
+        sort_descending_for_strategy(&mut records, strategy);
+
+        self.sorted_ranked_records = rank_sorted_descending(records, strategy);
+        self.strategy = strategy;
+    }
+
+    /// Hydrates this ranking with only the top `k` records, without sorting
+    /// the whole `records` set. Keeps a bounded min-heap of size `k` and
+    /// replaces its current smallest entry whenever a larger one is seen, so
+    /// this runs in O(n log k) time and O(k) memory instead of the O(n log n)
+    /// full sort `hydrate_record_set` does.
+    pub fn top_k(
+        &mut self,
+        records: HashSet<AttributionRecord<Attribution>>,
+        k: usize,
+        strategy: RankingStrategy,
+    ) {
+        let mut heap: BinaryHeap<MinHeapEntry<Attribution>> = BinaryHeap::with_capacity(k.min(records.len()));
+
+        for record in records {
+            let entry = MinHeapEntry(record);
+
+            if heap.len() < k {
+                heap.push(entry);
+            } else if let Some(smallest) = heap.peek() {
+                // `MinHeapEntry`'s `Ord` is reversed, so the entry that should
+                // replace the current minimum is the one that's *less* than
+                // it — this also carries the uuid tiebreak, unlike comparing
+                // `.attribution` alone.
+                if entry < *smallest {
+                    heap.pop();
+                    heap.push(entry);
+                }
             }
-        };
+        }
+
+        let mut sorted_descending = Vec::with_capacity(heap.len());
+        while let Some(entry) = heap.pop() {
+            sorted_descending.push(entry.0);
+        }
+        sorted_descending.reverse();
+        sort_descending_for_strategy(&mut sorted_descending, strategy);
+
+        self.sorted_ranked_records = rank_sorted_descending(sorted_descending, strategy);
+        self.strategy = strategy;
+    }
+
+    /// Normalizes every held rank into a `local_score`, the top player
+    /// always scoring `1.0`.
+    fn scored_records(&self) -> Vec<ScoredRankedAttributionRecord<Attribution>> {
+        let max_rank = self.sorted_ranked_records.iter().map(|r| r.rank).max();
+
+        self.sorted_ranked_records
+            .iter()
+            .map(|record| {
+                let local_score = match max_rank {
+                    Some(max_rank) if max_rank > 0 => {
+                        1.0 - ((record.rank - 1) as f64) / (max_rank as f64)
+                    }
+                    _ => 1.0,
+                };
+
+                ScoredRankedAttributionRecord {
+                    rank: record.rank,
+                    attribution_record: record.attribution_record.clone(),
+                    local_score,
+                }
+            })
+            .collect()
+    }
+
+    pub fn paginate(&self, offset: usize, limit: usize) -> RankingSlice<Attribution> {
+        RankingSlice(paginate_slice(&self.scored_records(), offset, limit))
+    }
+
+    /// Like [`Ranking::paginate`], but first drops every record whose
+    /// normalized `local_score` is below `min_score`, so clients can ask for
+    /// "only meaningfully ranked players".
+    pub fn paginate_above(&self, offset: usize, limit: usize, min_score: f64) -> RankingSlice<Attribution> {
+        let above_threshold = self
+            .scored_records()
+            .into_iter()
+            .filter(|record| record.local_score >= min_score)
+            .collect::<Vec<_>>();
+
+        RankingSlice(paginate_slice(&above_threshold, offset, limit))
+    }
+
+    /// Reflects a single player's updated attribution without rebuilding the
+    /// whole ranking: locates their existing record (or inserts a new one if
+    /// they weren't ranked yet), repositions it via binary search on the
+    /// sorted order, and recomputes ranks only for the span that move could
+    /// have affected.
+    pub fn apply_delta(&mut self, player: Player, new_attribution: Attribution) {
+        self.apply_deltas(iter::once((player, new_attribution)));
+    }
+
+    /// Folds a batch of per-player deltas into the existing sorted structure,
+    /// so high-frequency stat updates don't each force a global rebuild the
+    /// way repeated [`Ranking::hydrate_record_set`] calls would. Each delta
+    /// locates its existing record with a linear scan and repositions it via
+    /// binary search, so this costs O(m*n) for `m` deltas against an `n`-long
+    /// ranking rather than a true single-pass merge — fine for the small,
+    /// occasional batches this is meant for, but callers applying very large
+    /// batches against a large ranking should prefer
+    /// [`Ranking::hydrate_record_set`] instead.
+    pub fn apply_deltas(&mut self, deltas: impl IntoIterator<Item = (Player, Attribution)>) {
+        let mut min_affected_index = self.sorted_ranked_records.len();
+
+        for (player, new_attribution) in deltas {
+            if let Some(existing_index) = self
+                .sorted_ranked_records
+                .iter()
+                .position(|r| r.attribution_record.player.uuid == player.uuid)
+            {
+                self.sorted_ranked_records.remove(existing_index);
+                min_affected_index = min_affected_index.min(existing_index);
+            }
+
+            let new_record = AttributionRecord {
+                player,
+                attribution: new_attribution,
+            };
 
-        let first_ranked_record = RankedAttributionRecord {
-            rank: 1,
-            attribution_record: first_record.clone(),
+            let insert_index = self.binary_search_insert_index(&new_record);
+            min_affected_index = min_affected_index.min(insert_index);
+
+            self.sorted_ranked_records.insert(
+                insert_index,
+                RankedAttributionRecord {
+                    // placeholder; overwritten by `recompute_ranks_from` below
+                    rank: 0,
+                    attribution_record: new_record,
+                },
+            );
+        }
+
+        self.recompute_ranks_from(min_affected_index);
+    }
+
+    /// Finds where `record` belongs in `sorted_ranked_records` (descending by
+    /// attribution, and for [`RankingStrategy::Ordinal`] by `Player.uuid`
+    /// within ties) via binary search.
+    fn binary_search_insert_index(&self, record: &AttributionRecord<Attribution>) -> usize {
+        let search_result = match self.strategy {
+            RankingStrategy::Ordinal => self.sorted_ranked_records.binary_search_by(|probe| {
+                probe
+                    .attribution_record
+                    .attribution
+                    .cmp(&record.attribution)
+                    .reverse()
+                    .then_with(|| probe.attribution_record.player.uuid.cmp(&record.player.uuid))
+            }),
+            RankingStrategy::Competition | RankingStrategy::Dense => {
+                self.sorted_ranked_records.binary_search_by(|probe| {
+                    probe
+                        .attribution_record
+                        .attribution
+                        .cmp(&record.attribution)
+                        .reverse()
+                })
+            }
         };
 
-        let initial_scan_state = ScanState {
-            next_item_index: 0,
-            previous_attribution: first_record.attribution.clone(),
-            previous_item_rank: 1,
+        search_result.unwrap_or_else(|insert_index| insert_index)
+    }
+
+    /// Recomputes `.rank` for every record from `start_index` onward,
+    /// continuing the scan from whatever value/rank preceded it so records
+    /// before `start_index` keep the ranks they already have.
+    fn recompute_ranks_from(&mut self, start_index: usize) {
+        let preceding = start_index
+            .checked_sub(1)
+            .and_then(|i| self.sorted_ranked_records.get(i))
+            .map(|r| (r.attribution_record.attribution.clone(), r.rank));
+
+        let (mut previous_attribution, mut previous_rank) = match preceding {
+            Some((attribution, rank)) => (Some(attribution), rank),
+            None => (None, 0),
         };
 
-        let ranked_tail_records = tail_records.iter().scan(initial_scan_state, |st, record| {
-            let next_rank = if st.previous_attribution == record.attribution {
-                st.previous_item_rank
-            } else {
-                assert!(st.previous_attribution < record.attribution);
-                (st.next_item_index as u32) + 1
-            };
+        for index in start_index..self.sorted_ranked_records.len() {
+            let attribution = self.sorted_ranked_records[index]
+                .attribution_record
+                .attribution
+                .clone();
+            let is_tie = previous_attribution.as_ref() == Some(&attribution);
 
-            let next_ranked_record = RankedAttributionRecord {
-                rank: next_rank,
-                attribution_record: record.clone(),
+            let next_rank = match &previous_attribution {
+                None => 1,
+                Some(_) => match self.strategy {
+                    RankingStrategy::Competition => {
+                        if is_tie {
+                            previous_rank
+                        } else {
+                            (index as u32) + 1
+                        }
+                    }
+                    RankingStrategy::Dense => {
+                        if is_tie {
+                            previous_rank
+                        } else {
+                            previous_rank + 1
+                        }
+                    }
+                    RankingStrategy::Ordinal => previous_rank + 1,
+                },
             };
 
-            st.next_item_index += 1;
-            st.previous_attribution = record.attribution.clone();
-            st.previous_item_rank = next_rank;
-
-            Some(next_ranked_record)
-        });
+            self.sorted_ranked_records[index].rank = next_rank;
 
-        self.sorted_ranked_records = iter::once(first_ranked_record)
-            .chain(ranked_tail_records)
-            .collect()
+            previous_attribution = Some(attribution);
+            previous_rank = next_rank;
+        }
     }
 
-    pub fn paginate(&self, offset: usize, limit: usize) -> RankingSlice<Attribution> {
-        RankingSlice(self.sorted_ranked_records.as_slice()[offset..(offset + limit)].to_vec())
+    /// Builds one [`Ranking`] per [`AggregationTimeRange`] variant from a
+    /// single provider, so a caller gets daily/weekly/monthly/yearly/all-time
+    /// boards for a metric from one call instead of wiring up a separate
+    /// fetch-and-hydrate per board itself. This still issues one
+    /// `get_attribution_records_within` call per variant — it's on
+    /// `Provider` to make each of those cheap (e.g. a single indexed query)
+    /// if that matters for its backing store.
+    pub fn hydrate_all_time_ranges<Provider>(
+        provider: Provider,
+        strategy: RankingStrategy,
+    ) -> Vec<(AggregationTimeRange, Ranking<Attribution>)>
+    where
+        Provider: AttributionRecordProvider<Attribution> + Clone,
+    {
+        AggregationTimeRange::iter()
+            .map(|range| {
+                let records = provider.clone().get_attribution_records_within(range);
+                let mut ranking = Ranking::default();
+                ranking.hydrate_record_set(records.into_iter().collect(), strategy);
+                (range, ranking)
+            })
+            .collect()
     }
 }
 
 pub trait AttributionRecordProvider<Attribution: AggregatedPlayerAttribution> {
     fn get_all_attribution_records(self) -> Vec<AttributionRecord<Attribution>>;
+
+    /// Same as `get_all_attribution_records`, but scoped to attribution
+    /// accrued within `range` (e.g. "broken this week").
+    fn get_attribution_records_within(
+        self,
+        range: AggregationTimeRange,
+    ) -> Vec<AttributionRecord<Attribution>>;
 }
 
-#[derive(Debug, PartialEq, EnumString, EnumIter)]
+/// One metric's contribution to a [`FederatedRanking`]: the raw records for
+/// that metric plus the weight its normalized score carries in the combined
+/// `global_score`.
+pub struct WeightedMetric<Attribution: AggregatedPlayerAttribution> {
+    records: Vec<AttributionRecord<Attribution>>,
+    weight: f64,
+}
+
+impl<Attribution: AggregatedPlayerAttribution> WeightedMetric<Attribution> {
+    pub fn new(records: Vec<AttributionRecord<Attribution>>, weight: f64) -> Self {
+        WeightedMetric { records, weight }
+    }
+}
+
+/// Type-erases a [`WeightedMetric`]'s attribution type so that metrics of
+/// different kinds (break count, build count, ...) can be federated together.
+trait MetricSource {
+    /// For every player in this metric, `self.weight * (value / max_value_for_this_metric)`.
+    /// Players absent from this metric simply don't appear, and contribute 0
+    /// once merged in [`FederatedRanking::hydrate_from_sources`].
+    fn weighted_local_scores(&self) -> Vec<(Player, f64)>;
+}
+
+impl<Attribution: AggregatedPlayerAttribution> MetricSource for WeightedMetric<Attribution> {
+    fn weighted_local_scores(&self) -> Vec<(Player, f64)> {
+        let max_value = self
+            .records
+            .iter()
+            .map(|record| record.attribution.as_f64())
+            .fold(0.0_f64, f64::max);
+
+        self.records
+            .iter()
+            .map(|record| {
+                let local_score = if max_value > 0.0 {
+                    record.attribution.as_f64() / max_value
+                } else {
+                    0.0
+                };
+                (record.player.clone(), self.weight * local_score)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub struct GlobalScoreRecord {
+    pub player: Player,
+    pub global_score: f64,
+}
+
+#[derive(Clone)]
+pub struct RankedGlobalScoreRecord {
+    pub rank: u32,
+    pub record: GlobalScoreRecord,
+}
+
+pub struct FederatedRankingSlice(pub Vec<RankedGlobalScoreRecord>);
+
+/// Merges several [`AttributionRecordProvider`]s of different metric types
+/// into a single leaderboard keyed by `Player.uuid`, so a client wanting
+/// "overall top players" doesn't have to fetch and merge per-metric boards
+/// by hand.
+#[derive(Default)]
+pub struct FederatedRanking {
+    /// invariant: same shape as `Ranking::sorted_ranked_records`, scored by
+    /// `global_score` descending instead of a single `Attribution`.
+    sorted_ranked_records: Vec<RankedGlobalScoreRecord>,
+}
+
+impl FederatedRanking {
+    pub fn hydrate_from_sources(&mut self, sources: &[WeightedMetricErased]) {
+        let mut global_scores: HashMap<Uuid, GlobalScoreRecord> = HashMap::new();
+
+        for source in sources {
+            for (player, weighted_local_score) in source.0.weighted_local_scores() {
+                let entry = global_scores.entry(player.uuid).or_insert_with(|| GlobalScoreRecord {
+                    player: player.clone(),
+                    global_score: 0.0,
+                });
+                entry.global_score += weighted_local_score;
+            }
+        }
+
+        let mut records = global_scores.into_values().collect::<Vec<_>>();
+        records.sort_by(|a, b| b.global_score.total_cmp(&a.global_score));
+
+        // Federated boards always use competition ranking — the same tie
+        // semantics `Ranking::hydrate_record_set` defaulted to before
+        // `RankingStrategy` existed.
+        let ranks = assign_ranks_descending(&records, RankingStrategy::Competition, |previous, current| {
+            previous.global_score.total_cmp(&current.global_score)
+        });
+
+        self.sorted_ranked_records = records
+            .into_iter()
+            .zip(ranks)
+            .map(|(record, rank)| RankedGlobalScoreRecord { rank, record })
+            .collect();
+    }
+
+    pub fn paginate(&self, offset: usize, limit: usize) -> FederatedRankingSlice {
+        FederatedRankingSlice(paginate_slice(&self.sorted_ranked_records, offset, limit))
+    }
+}
+
+/// Erases a [`WeightedMetric`]'s attribution type. Build one per metric via
+/// [`WeightedMetricErased::new`] and pass the collection to
+/// [`FederatedRanking::hydrate_from_sources`].
+pub struct WeightedMetricErased(Box<dyn MetricSource>);
+
+impl WeightedMetricErased {
+    pub fn new<Attribution: AggregatedPlayerAttribution + 'static>(
+        metric: WeightedMetric<Attribution>,
+    ) -> Self {
+        WeightedMetricErased(Box::new(metric))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumString, EnumIter)]
 #[strum(serialize_all = "snake_case")]
 pub enum AggregationTimeRange {
     All,
@@ -137,3 +637,179 @@ pub enum AggregationTimeRange {
     LastOneWeek,
     LastOneDay,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STRATEGIES: [RankingStrategy; 3] = [
+        RankingStrategy::Competition,
+        RankingStrategy::Dense,
+        RankingStrategy::Ordinal,
+    ];
+
+    fn player(n: u8) -> Player {
+        Player {
+            uuid: Uuid::from_u128(n as u128),
+            name: format!("player-{n}"),
+        }
+    }
+
+    fn record(n: u8, value: u64) -> AttributionRecord<BreakCount> {
+        AttributionRecord {
+            player: player(n),
+            attribution: BreakCount(value),
+        }
+    }
+
+    /// Boils a [`Ranking`] down to `(rank, uuid)` pairs in order, which is all
+    /// that matters for asserting two rankings agree.
+    fn ranks_and_uuids(ranking: &Ranking<BreakCount>) -> Vec<(u32, Uuid)> {
+        ranking
+            .sorted_ranked_records
+            .iter()
+            .map(|r| (r.rank, r.attribution_record.player.uuid))
+            .collect()
+    }
+
+    fn tied_records() -> HashSet<AttributionRecord<BreakCount>> {
+        // Players 2 and 3 are tied, exercising each strategy's tie handling.
+        HashSet::from([record(1, 30), record(2, 20), record(3, 20), record(4, 10)])
+    }
+
+    #[test]
+    fn top_k_matches_full_rehydrate_for_every_strategy() {
+        // Only Ordinal guarantees a total order among ties, so compare the
+        // (rank, uuid) pairs as sets rather than sequences: `hydrate_record_set`
+        // and `top_k` are allowed to arrange same-rank records differently,
+        // as long as every record ends up with the same rank either way.
+        for strategy in ALL_STRATEGIES {
+            let records = tied_records();
+
+            let mut via_top_k = Ranking::default();
+            via_top_k.top_k(records.clone(), records.len(), strategy);
+
+            let mut via_hydrate = Ranking::default();
+            via_hydrate.hydrate_record_set(records, strategy);
+
+            let mut from_top_k = ranks_and_uuids(&via_top_k);
+            let mut from_hydrate = ranks_and_uuids(&via_hydrate);
+            from_top_k.sort();
+            from_hydrate.sort();
+
+            assert_eq!(
+                from_top_k, from_hydrate,
+                "top_k should agree with hydrate_record_set under {strategy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn top_k_keeps_only_the_k_largest() {
+        let mut ranking = Ranking::default();
+        ranking.top_k(tied_records(), 2, RankingStrategy::Competition);
+
+        let kept = ranking
+            .sorted_ranked_records
+            .iter()
+            .map(|r| r.attribution_record.player.uuid)
+            .collect::<HashSet<_>>();
+
+        // Player 1 (30) is the clear top record; players 2 and 3 are tied at
+        // 20, and `MinHeapEntry`'s uuid tiebreak deterministically keeps 3.
+        assert_eq!(kept, HashSet::from([player(1).uuid, player(3).uuid]));
+    }
+
+    #[test]
+    fn apply_delta_matches_full_rehydrate_for_every_strategy() {
+        for strategy in ALL_STRATEGIES {
+            let records = tied_records();
+
+            let mut via_deltas = Ranking::default();
+            via_deltas.hydrate_record_set(HashSet::new(), strategy);
+            for record in records.clone() {
+                via_deltas.apply_delta(record.player, record.attribution);
+            }
+
+            let mut via_hydrate = Ranking::default();
+            via_hydrate.hydrate_record_set(records, strategy);
+
+            assert_eq!(
+                ranks_and_uuids(&via_deltas),
+                ranks_and_uuids(&via_hydrate),
+                "apply_delta should agree with hydrate_record_set under {strategy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn apply_deltas_matches_full_rehydrate_for_every_strategy() {
+        for strategy in ALL_STRATEGIES {
+            let records = tied_records();
+
+            let mut via_deltas = Ranking::default();
+            via_deltas.hydrate_record_set(HashSet::new(), strategy);
+            via_deltas.apply_deltas(
+                records
+                    .clone()
+                    .into_iter()
+                    .map(|r| (r.player, r.attribution)),
+            );
+
+            let mut via_hydrate = Ranking::default();
+            via_hydrate.hydrate_record_set(records, strategy);
+
+            assert_eq!(
+                ranks_and_uuids(&via_deltas),
+                ranks_and_uuids(&via_hydrate),
+                "apply_deltas should agree with hydrate_record_set under {strategy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn apply_delta_updates_an_existing_players_attribution_in_place() {
+        let mut ranking = Ranking::default();
+        ranking.hydrate_record_set(
+            HashSet::from([record(1, 10), record(2, 5)]),
+            RankingStrategy::Competition,
+        );
+
+        ranking.apply_delta(player(2), BreakCount(100));
+
+        assert_eq!(
+            ranks_and_uuids(&ranking),
+            vec![(1, player(2).uuid), (2, player(1).uuid)]
+        );
+    }
+
+    #[test]
+    fn paginate_past_the_end_returns_empty_instead_of_panicking() {
+        let mut ranking = Ranking::default();
+        ranking.hydrate_record_set(tied_records(), RankingStrategy::Competition);
+
+        assert!(ranking.paginate(100, 10).0.is_empty());
+        assert_eq!(ranking.paginate(3, 10).0.len(), 1);
+    }
+
+    #[test]
+    fn paginate_above_past_the_end_returns_empty_instead_of_panicking() {
+        let mut ranking = Ranking::default();
+        ranking.hydrate_record_set(tied_records(), RankingStrategy::Competition);
+
+        // Only player 1 (the highest) clears a 0.9 local_score threshold.
+        assert!(ranking.paginate_above(100, 10, 0.9).0.is_empty());
+        assert_eq!(ranking.paginate_above(0, 10, 0.9).0.len(), 1);
+    }
+
+    #[test]
+    fn federated_paginate_past_the_end_returns_empty_instead_of_panicking() {
+        let metric = WeightedMetric::new(vec![record(1, 10), record(2, 5)], 1.0);
+
+        let mut federated = FederatedRanking::default();
+        federated.hydrate_from_sources(&[WeightedMetricErased::new(metric)]);
+
+        assert!(federated.paginate(100, 10).0.is_empty());
+        assert_eq!(federated.paginate(1, 10).0.len(), 1);
+    }
+}